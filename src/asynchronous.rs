@@ -0,0 +1,253 @@
+//! Async counterpart of the blocking [`DfuNusb`](crate::DfuNusb) path.
+//!
+//! nusb is async-first, so instead of going through
+//! `control_in_blocking`/`control_out_blocking` this drives the DFU
+//! control transfers (`read_control`/`write_control`, and with them
+//! download, upload and status polling) through
+//! `Interface::control_in`/`control_out`, letting firmware updates run on
+//! whatever reactor the caller is already using (tokio, async-std, ...)
+//! instead of blocking a thread. Opening the device (claiming the
+//! interface, selecting the alt setting and reading the alt-setting
+//! string descriptor) and `usb_reset`, which calls `Device::reset`, still
+//! use nusb's blocking calls. Enable it with the `async` feature.
+
+use std::time::Duration;
+
+use dfu_core::DfuProtocol;
+use dfu_core::functional_descriptor::FunctionalDescriptor;
+use dfu_core::memory_layout::MemoryLayout;
+use nusb::{Device, Interface};
+use nusb::transfer::{ControlIn, ControlOut};
+
+use crate::{explode_request_type, DfuNusb, Error};
+
+const DFU_DNLOAD: u8 = 1;
+const DFU_UPLOAD: u8 = 2;
+const DFU_GETSTATUS: u8 = 3;
+
+/// dfuIDLE: idle, ready for a new download/upload.
+const STATE_IDLE: u8 = 2;
+/// dfuDNLOAD-SYNC: the device has received a block and is deciding
+/// whether it needs time to process it.
+const STATE_DNLOAD_SYNC: u8 = 3;
+/// dfuDNBUSY: the device is still processing the last block.
+const STATE_DNBUSY: u8 = 4;
+/// dfuDNLOAD-IDLE: ready for the next block (or the final zero-length one).
+const STATE_DNLOAD_IDLE: u8 = 5;
+/// dfuMANIFEST-SYNC: the device has received the final block and is
+/// deciding whether it needs time to manifest the new firmware.
+const STATE_MANIFEST_SYNC: u8 = 6;
+/// dfuMANIFEST: the device is manifesting the new firmware.
+const STATE_MANIFEST: u8 = 7;
+/// dfuMANIFEST-WAIT-RESET: manifestation is complete and the device is
+/// waiting for a USB reset (or power cycle) to run the new firmware; it
+/// may stop responding to requests at this point.
+const STATE_MANIFEST_WAIT_RESET: u8 = 8;
+/// dfuERROR: the device has latched an error status; needs `DFU_CLRSTATUS`.
+const STATE_ERROR: u8 = 10;
+
+/// Async version of [`DfuNusb`], built on nusb's native futures instead of
+/// its blocking control transfer calls.
+pub struct DfuNusbAsync {
+    dev: Device,
+    iface: Interface,
+    protocol: DfuProtocol<MemoryLayout>,
+    functional_descriptor: FunctionalDescriptor,
+}
+
+impl DfuNusbAsync {
+    pub async fn open(vid: u16, pid: u16, iface: u8, alt: u8) -> Result<Self, Error> {
+        let device = Self::open_device(vid, pid)?;
+        Self::from_usb_device(device, iface, alt).await
+    }
+
+    fn open_device(vid: u16, pid: u16) -> Result<Device, Error> {
+        nusb::list_devices()?
+            .find(|dev_info| dev_info.vendor_id() == vid && dev_info.product_id() == pid)
+            .ok_or(Error::CouldNotOpenDevice)
+            .and_then(|dev_info| dev_info.open().map_err(|e| e.into()))
+    }
+
+    pub async fn from_usb_device(device: Device, iface_num: u8, alt: u8) -> Result<Self, Error> {
+        let iface = device.claim_interface(iface_num)?;
+        iface.set_alt_setting(alt)?;
+        for config in device.configurations() {
+            let Some(interface) = config.interfaces().find(|x| x.interface_number() == iface_num)
+            else {
+                continue;
+            };
+            let Some(setting) = interface
+                .alt_settings()
+                .find(|x| x.alternate_setting() == alt)
+            else {
+                continue;
+            };
+            if let Some(func_desc) =
+                DfuNusb::find_functional_descriptor(&config, iface_num, alt).transpose()?
+            {
+                if let Some(string_ix) = setting.string_index() {
+                    let iface_string = device
+                        .get_string_descriptor(string_ix, 0, Duration::from_millis(1000))?
+                        .trim_end_matches('\0')
+                        .to_string();
+
+                    let protocol = DfuProtocol::new(&iface_string, func_desc.dfu_version)?;
+
+                    return Ok(DfuNusbAsync {
+                        dev: device.clone(),
+                        iface,
+                        protocol,
+                        functional_descriptor: func_desc,
+                    });
+                }
+            }
+        }
+
+        Err(Error::NoDfuCapableDeviceFound)
+    }
+
+    pub async fn read_control(
+        &self,
+        request_type: u8,
+        request: u8,
+        value: u16,
+        length: usize,
+    ) -> Result<Vec<u8>, Error> {
+        let (control_type, recipient) = explode_request_type(request_type);
+        let data = self
+            .iface
+            .control_in(ControlIn {
+                control_type,
+                recipient,
+                request,
+                value,
+                index: self.iface.interface_number() as u16,
+                length: length as u16,
+            })
+            .await
+            .into_result()?;
+        Ok(data)
+    }
+
+    pub async fn write_control(
+        &self,
+        request_type: u8,
+        request: u8,
+        value: u16,
+        buffer: Vec<u8>,
+    ) -> Result<usize, Error> {
+        let (control_type, recipient) = explode_request_type(request_type);
+        let response = self
+            .iface
+            .control_out(ControlOut {
+                control_type,
+                recipient,
+                request,
+                value,
+                index: self.iface.interface_number() as u16,
+                data: &buffer,
+            })
+            .await
+            .into_result()?;
+        Ok(response.actual_length())
+    }
+
+    pub async fn usb_reset(&self) -> Result<(), Error> {
+        self.dev.reset()?;
+        Ok(())
+    }
+
+    pub fn protocol(&self) -> &DfuProtocol<MemoryLayout> {
+        &self.protocol
+    }
+
+    pub fn functional_descriptor(&self) -> &FunctionalDescriptor {
+        &self.functional_descriptor
+    }
+
+    /// Downloads `firmware` to the device, one `wTransferSize`-sized block
+    /// at a time, polling `DFU_GETSTATUS` between blocks the same way
+    /// [`dfu_core::sync::DfuSync`] does for the blocking path, but without
+    /// ever blocking the calling thread.
+    ///
+    /// Only plain DFU 1.1 devices are supported; DfuSe devices need the
+    /// set-address/erase command sequence the sync path drives through
+    /// `dfu_core` and are rejected with [`Error::DfuseUnsupportedAsync`].
+    pub async fn download(&mut self, firmware: &[u8]) -> Result<(), Error> {
+        self.reject_dfuse()?;
+        let transfer_size = (self.functional_descriptor.transfer_size as usize).max(1);
+        let mut block_num = 0u16;
+        for chunk in firmware.chunks(transfer_size) {
+            self.write_control(0b0010_0001, DFU_DNLOAD, block_num, chunk.to_vec())
+                .await?;
+            self.wait_while_busy().await?;
+            block_num = block_num.wrapping_add(1);
+        }
+        // A final zero-length DNLOAD tells the device the transfer is
+        // complete and moves it into manifestation.
+        self.write_control(0b0010_0001, DFU_DNLOAD, block_num, Vec::new())
+            .await?;
+        self.wait_while_busy().await?;
+        Ok(())
+    }
+
+    /// Uploads the device's current firmware, one `wTransferSize`-sized
+    /// block at a time, stopping at the first short (or empty) block as
+    /// the DFU spec requires.
+    ///
+    /// Only plain DFU 1.1 devices are supported; see [`Self::download`].
+    pub async fn upload(&mut self) -> Result<Vec<u8>, Error> {
+        self.reject_dfuse()?;
+        let transfer_size = (self.functional_descriptor.transfer_size as usize).max(1);
+        let mut firmware = Vec::new();
+        let mut block_num = 0u16;
+        loop {
+            let chunk = self
+                .read_control(0b1010_0001, DFU_UPLOAD, block_num, transfer_size)
+                .await?;
+            let got = chunk.len();
+            firmware.extend_from_slice(&chunk);
+            if got < transfer_size {
+                break;
+            }
+            block_num = block_num.wrapping_add(1);
+        }
+        // A final GETSTATUS returns the device to dfuIDLE.
+        self.read_control(0b1010_0001, DFU_GETSTATUS, 0, 6).await?;
+        Ok(firmware)
+    }
+
+    fn reject_dfuse(&self) -> Result<(), Error> {
+        if matches!(self.protocol, DfuProtocol::Dfuse { .. }) {
+            return Err(Error::DfuseUnsupportedAsync);
+        }
+        Ok(())
+    }
+
+    /// Polls `DFU_GETSTATUS` until the device leaves the busy states
+    /// (`dfuDNLOAD-SYNC`/`dfuDNBUSY`/`dfuMANIFEST-SYNC`/`dfuMANIFEST`),
+    /// honoring the device's requested `bwPollTimeout` between polls
+    /// instead of spinning the bus. `dfuMANIFEST-WAIT-RESET` is treated
+    /// as done rather than polled further, since a device in that state
+    /// may stop responding until it is reset.
+    async fn wait_while_busy(&self) -> Result<(), Error> {
+        loop {
+            let status = self.read_control(0b1010_0001, DFU_GETSTATUS, 0, 6).await?;
+            if status.len() < 6 {
+                return Err(Error::ShortGetStatusResponse(status.len()));
+            }
+            let poll_timeout_ms = u32::from_le_bytes([status[1], status[2], status[3], 0]);
+            match status[4] {
+                STATE_DNLOAD_SYNC | STATE_DNBUSY | STATE_MANIFEST_SYNC | STATE_MANIFEST => {
+                    if poll_timeout_ms > 0 {
+                        futures_timer::Delay::new(Duration::from_millis(poll_timeout_ms as u64))
+                            .await;
+                    }
+                }
+                STATE_DNLOAD_IDLE | STATE_IDLE | STATE_MANIFEST_WAIT_RESET => return Ok(()),
+                STATE_ERROR => return Err(Error::DeviceReportedError(status[0])),
+                _ => return Err(Error::CouldNotOpenDevice),
+            }
+        }
+    }
+}
@@ -6,8 +6,54 @@ use nusb::{Device, Interface};
 use nusb::transfer::{Control, ControlType, Recipient, TransferError};
 use thiserror::Error;
 
+#[cfg(feature = "async")]
+pub mod asynchronous;
+#[cfg(feature = "async")]
+pub use asynchronous::DfuNusbAsync;
+
 pub type Dfu = dfu_core::sync::DfuSync<DfuNusb, Error>;
 
+/// USB interface class used by DFU interfaces (Application Specific).
+const DFU_INTERFACE_CLASS: u8 = 0xfe;
+/// USB interface subclass used by DFU interfaces.
+const DFU_INTERFACE_SUBCLASS: u8 = 0x01;
+/// bInterfaceProtocol of a DFU interface exposed by an application still
+/// running in run-time mode.
+const DFU_RUNTIME_PROTOCOL: u8 = 1;
+/// bInterfaceProtocol of a DFU interface exposed once the device is in
+/// DFU mode.
+const DFU_MODE_PROTOCOL: u8 = 2;
+/// DFU_DETACH class request (`bRequest`).
+const DFU_DETACH: u8 = 0;
+/// bDescriptorType of a standard configuration descriptor.
+#[cfg(test)]
+const DESC_TYPE_CONFIGURATION: u8 = 0x02;
+/// bDescriptorType of a standard interface descriptor.
+const DESC_TYPE_INTERFACE: u8 = 0x04;
+/// bDescriptorType of a DFU functional descriptor.
+const DESC_TYPE_DFU_FUNCTIONAL: u8 = 0x21;
+/// bLength of a DFU functional descriptor.
+const DFU_FUNCTIONAL_DESCRIPTOR_LEN: usize = 9;
+
+/// A DFU-capable interface discovered by [`DfuNusb::list_dfu_devices`].
+///
+/// This carries everything a caller needs to show a picker (like `lsusb`
+/// does) and then open the chosen device with
+/// [`DfuNusb::from_usb_device`] or [`DfuNusb::open_by_serial`].
+#[derive(Debug, Clone)]
+pub struct DfuDeviceInfo {
+    pub vendor_id: u16,
+    pub product_id: u16,
+    pub manufacturer_string: Option<String>,
+    pub product_string: Option<String>,
+    pub serial_number: Option<String>,
+    pub interface_number: u8,
+    pub alt_setting: u8,
+    /// The alt-setting string, i.e. the DfuSe memory-layout name.
+    pub alt_setting_name: Option<String>,
+    pub functional_descriptor: FunctionalDescriptor,
+}
+
 #[derive(Debug, Error)]
 pub enum Error {
     #[error("Could not find device or an error occurred.")]
@@ -28,6 +74,14 @@ pub enum Error {
     FunctionalDescriptor(#[from] dfu_core::functional_descriptor::Error),
     #[error("No DFU capable device found.")]
     NoDfuCapableDeviceFound,
+    #[error("Device did not re-enumerate in DFU mode before the detach timeout elapsed.")]
+    DeviceDidNotReenumerate,
+    #[error("Device reported a DFU error status: {0:#x}")]
+    DeviceReportedError(u8),
+    #[error("Device returned a truncated DFU_GETSTATUS response (expected 6 bytes, got {0}).")]
+    ShortGetStatusResponse(usize),
+    #[error("DfuSe devices are not yet supported by the async driver; use the sync `Dfu` path instead.")]
+    DfuseUnsupportedAsync,
 }
 
 pub struct DfuNusb {
@@ -36,6 +90,17 @@ pub struct DfuNusb {
     protocol: DfuProtocol<MemoryLayout>,
     timeout: Duration,
     functional_descriptor: FunctionalDescriptor,
+    retries: u32,
+    retry_backoff: Duration,
+    reset_on_drop: bool,
+}
+
+impl Drop for DfuNusb {
+    fn drop(&mut self) {
+        if self.reset_on_drop {
+            let _ = self.dev.reset();
+        }
+    }
 }
 
 impl dfu_core::DfuIo for DfuNusb {
@@ -55,18 +120,29 @@ impl dfu_core::DfuIo for DfuNusb {
         buffer: &mut [u8],
     ) -> Result<Self::Read, Self::Error> {
         let (control_type, recipient) = explode_request_type(request_type);
-        let res = self.iface.control_in_blocking(
-            Control {
-                control_type,
-                recipient,
-                request,
-                value,
-                index: self.iface.interface_number() as u16,
-            },
-            buffer,
-            self.timeout,
-        );
-        Ok(res?)
+        let mut attempt = 0;
+        loop {
+            let res = self.iface.control_in_blocking(
+                Control {
+                    control_type,
+                    recipient,
+                    request,
+                    value,
+                    index: self.iface.interface_number() as u16,
+                },
+                buffer,
+                self.timeout,
+            );
+            match res {
+                Err(TransferError::Stall) | Err(TransferError::Cancelled)
+                    if attempt < self.retries =>
+                {
+                    attempt += 1;
+                    std::thread::sleep(self.retry_backoff * attempt);
+                }
+                res => return Ok(res?),
+            }
+        }
     }
 
     #[allow(unused_variables)]
@@ -78,18 +154,29 @@ impl dfu_core::DfuIo for DfuNusb {
         buffer: &[u8],
     ) -> Result<Self::Write, Self::Error> {
         let (control_type, recipient) = explode_request_type(request_type);
-        let res = self.iface.control_out_blocking(
-            Control {
-                control_type,
-                recipient,
-                request,
-                value,
-                index: self.iface.interface_number() as u16,
-            },
-            buffer,
-            self.timeout,
-        );
-        Ok(res?)
+        let mut attempt = 0;
+        loop {
+            let res = self.iface.control_out_blocking(
+                Control {
+                    control_type,
+                    recipient,
+                    request,
+                    value,
+                    index: self.iface.interface_number() as u16,
+                },
+                buffer,
+                self.timeout,
+            );
+            match res {
+                Err(TransferError::Stall) | Err(TransferError::Cancelled)
+                    if attempt < self.retries =>
+                {
+                    attempt += 1;
+                    std::thread::sleep(self.retry_backoff * attempt);
+                }
+                res => return Ok(res?),
+            }
+        }
     }
 
     fn usb_reset(&self) -> Result<Self::Reset, Self::Error> {
@@ -107,8 +194,7 @@ impl dfu_core::DfuIo for DfuNusb {
 
 impl DfuNusb {
     pub fn open(vid: u16, pid: u16, iface: u8, alt: u8) -> Result<Dfu, Error> {
-        let device = Self::open_device(vid, pid)?;
-        Self::from_usb_device(device, iface, alt)
+        DfuNusbBuilder::default().open(vid, pid, iface, alt)
     }
 
     fn open_device(
@@ -126,60 +212,338 @@ impl DfuNusb {
         iface_num: u8,
         alt: u8,
     ) -> Result<Dfu, Error> {
-        let timeout = std::time::Duration::from_secs(3);
-        let iface = device.claim_interface(iface_num)?;
-        iface.set_alt_setting(alt)?;
-        for config in device.configurations() {
-            if let Some(func_desc) = Self::find_functional_descriptor(&device, &config, timeout)
-                .transpose()? {
-                    let interface = config.interfaces()
-                        .find(|x| x.interface_number() == iface_num)
-                        .ok_or(Error::InvalidInterface)?;
-                    let setting = interface.alt_settings()
-                        .find(|x| x.alternate_setting() == alt)
-                        .ok_or(Error::InvalidAlt)?;
-                    if let Some(string_ix) = setting.string_index() {
-                        let iface_string = device.get_string_descriptor(
-                            string_ix, 0, Duration::from_millis(1000)
-                        )?.trim_end_matches('\0').to_string();
-
-                        let protocol = dfu_core::DfuProtocol::new(
-                            &iface_string,
-                            func_desc.dfu_version,
-                        )?;
-
-                        let io = DfuNusb {
-                            dev: device.clone(),
-                            iface: iface,
-                            protocol,
-                            timeout,
-                            functional_descriptor: func_desc,
+        DfuNusbBuilder::default().from_usb_device(device, iface_num, alt)
+    }
+
+    /// Walks every USB device, configuration, interface and alt setting
+    /// looking for DFU-capable interfaces (class `0xfe`, subclass `0x01`,
+    /// carrying a DFU functional descriptor), without requiring the
+    /// caller to already know the VID/PID/interface/alt of the target.
+    pub fn list_dfu_devices() -> Result<Vec<DfuDeviceInfo>, Error> {
+        let mut devices = Vec::new();
+        for dev_info in nusb::list_devices()? {
+            let Ok(device) = dev_info.open() else {
+                continue;
+            };
+            for config in device.configurations() {
+                for interface in config.interfaces() {
+                    for setting in interface.alt_settings() {
+                        if setting.class() != DFU_INTERFACE_CLASS
+                            || setting.subclass() != DFU_INTERFACE_SUBCLASS
+                        {
+                            continue;
+                        }
+                        let Some(func_desc) = Self::find_functional_descriptor(
+                            &config,
+                            interface.interface_number(),
+                            setting.alternate_setting(),
+                        )
+                        .transpose()?
+                        else {
+                            continue;
                         };
+                        let alt_setting_name = setting.string_index().and_then(|string_ix| {
+                            device
+                                .get_string_descriptor(string_ix, 0, Duration::from_millis(1000))
+                                .ok()
+                                .map(|s| s.trim_end_matches('\0').to_string())
+                        });
 
-                        return Ok(dfu_core::sync::DfuSync::new(io));
+                        devices.push(DfuDeviceInfo {
+                            vendor_id: dev_info.vendor_id(),
+                            product_id: dev_info.product_id(),
+                            manufacturer_string: dev_info.manufacturer_string().map(str::to_string),
+                            product_string: dev_info.product_string().map(str::to_string),
+                            serial_number: dev_info.serial_number().map(str::to_string),
+                            interface_number: interface.interface_number(),
+                            alt_setting: setting.alternate_setting(),
+                            alt_setting_name,
+                            functional_descriptor: func_desc,
+                        });
                     }
                 }
+            }
         }
 
-        Err(Error::NoDfuCapableDeviceFound)
+        Ok(devices)
+    }
+
+    /// Opens a device that may still be in DFU run-time (application)
+    /// mode. If the target interface/alt advertises the run-time DFU
+    /// protocol (`bInterfaceProtocol == 1`), this issues `DFU_DETACH`
+    /// and, unless the functional descriptor's `bitWillDetach` is set,
+    /// follows up with a bus reset, then polls `nusb::list_devices()`
+    /// for up to `wDetachTimeOut` milliseconds to rediscover the same
+    /// physical device now presenting a DFU-mode interface (`protocol
+    /// == 2`) — which may have re-enumerated under a different PID.
+    /// If the interface is already in DFU mode, this is equivalent to
+    /// [`DfuNusb::open`].
+    pub fn detach_and_open(vid: u16, pid: u16, iface_num: u8, alt: u8) -> Result<Dfu, Error> {
+        let dev_info = nusb::list_devices()?
+            .find(|dev_info| dev_info.vendor_id() == vid && dev_info.product_id() == pid)
+            .ok_or(Error::CouldNotOpenDevice)?;
+        let serial = dev_info.serial_number().map(str::to_string);
+        let device = dev_info.open()?;
+
+        if Self::alt_setting_protocol(&device, iface_num, alt) != Some(DFU_RUNTIME_PROTOCOL) {
+            return Self::from_usb_device(device, iface_num, alt);
+        }
+
+        let timeout = Duration::from_secs(3);
+        let func_desc = device
+            .configurations()
+            .find_map(|config| {
+                Self::find_functional_descriptor(&config, iface_num, alt)
+                    .transpose()
+                    .ok()
+                    .flatten()
+            })
+            .ok_or(Error::NoDfuCapableDeviceFound)?;
+
+        let detach_timeout = Duration::from_millis(func_desc.detach_timeout.max(1) as u64);
+        let will_detach = func_desc.will_detach;
+
+        let iface = device.claim_interface(iface_num)?;
+        iface.control_out_blocking(
+            Control {
+                control_type: ControlType::Class,
+                recipient: Recipient::Interface,
+                request: DFU_DETACH,
+                value: func_desc.detach_timeout,
+                index: iface_num as u16,
+            },
+            &[],
+            timeout,
+        )?;
+        if !will_detach {
+            device.reset()?;
+        }
+        drop(iface);
+        drop(device);
+
+        Self::wait_for_dfu_mode(vid, serial.as_deref(), iface_num, alt, detach_timeout)
+    }
+
+    fn alt_setting_protocol(device: &Device, iface_num: u8, alt: u8) -> Option<u8> {
+        device.configurations().find_map(|config| {
+            config
+                .interfaces()
+                .find(|interface| interface.interface_number() == iface_num)
+                .and_then(|interface| {
+                    interface
+                        .alt_settings()
+                        .find(|setting| setting.alternate_setting() == alt)
+                        .map(|setting| setting.protocol())
+                })
+        })
+    }
+
+    fn wait_for_dfu_mode(
+        vid: u16,
+        serial: Option<&str>,
+        iface_num: u8,
+        alt: u8,
+        timeout: Duration,
+    ) -> Result<Dfu, Error> {
+        let deadline = std::time::Instant::now() + timeout;
+        loop {
+            let candidate = nusb::list_devices()?.find(|dev_info| {
+                dev_info.vendor_id() == vid
+                    && serial.is_none_or(|serial| dev_info.serial_number() == Some(serial))
+            });
+            if let Some(dev_info) = candidate {
+                if let Ok(device) = dev_info.open() {
+                    if Self::alt_setting_protocol(&device, iface_num, alt) == Some(DFU_MODE_PROTOCOL) {
+                        if let Ok(dfu) = Self::from_usb_device(device, iface_num, alt) {
+                            return Ok(dfu);
+                        }
+                    }
+                }
+            }
+            if std::time::Instant::now() >= deadline {
+                return Err(Error::DeviceDidNotReenumerate);
+            }
+            std::thread::sleep(Duration::from_millis(50));
+        }
+    }
+
+    /// Opens a DFU-capable device by USB serial number, for multi-board
+    /// setups where VID/PID collide between boards.
+    pub fn open_by_serial(serial: &str, iface: u8, alt: u8) -> Result<Dfu, Error> {
+        let device = nusb::list_devices()?
+            .find(|dev_info| dev_info.serial_number() == Some(serial))
+            .ok_or(Error::CouldNotOpenDevice)
+            .and_then(|dev_info| dev_info.open().map_err(|e| e.into()))?;
+        Self::from_usb_device(device, iface, alt)
     }
 
+    /// Walks the configuration descriptor's raw byte stream as the TLV
+    /// sequence it actually is (`bLength`/`bDescriptorType` at the start
+    /// of each descriptor, advancing by `bLength` each step) and returns
+    /// the DFU functional descriptor (type `0x21`) that belongs to
+    /// `iface_num`/`alt`, rather than assuming every descriptor is a
+    /// fixed 9 bytes wide.
     pub fn find_functional_descriptor(
-        _device: &Device,
         config: &nusb::descriptors::Configuration,
-        _timeout: Duration,
+        iface_num: u8,
+        alt: u8,
     ) -> Option<Result<FunctionalDescriptor, Error>> {
-        for desc_data in config.descriptors().as_bytes().chunks(9) {
-            if let Some(func_desc) = FunctionalDescriptor::from_bytes(desc_data) {
-                return Some(func_desc.map_err(Into::into));
+        let bytes = config.descriptors().as_bytes();
+        let mut offset = 0;
+        let mut in_target_interface = false;
+
+        while offset + 2 <= bytes.len() {
+            let length = bytes[offset] as usize;
+            if length == 0 || offset + length > bytes.len() {
+                // A zero bLength would loop forever; a length that runs
+                // past the end means the descriptor set is truncated.
+                // Either way there is nothing more we can safely parse.
+                break;
+            }
+            let descriptor = &bytes[offset..offset + length];
+            let descriptor_type = descriptor[1];
+
+            if descriptor_type == DESC_TYPE_INTERFACE && length >= 4 {
+                in_target_interface = descriptor[2] == iface_num && descriptor[3] == alt;
+            } else if in_target_interface
+                && descriptor_type == DESC_TYPE_DFU_FUNCTIONAL
+                && length == DFU_FUNCTIONAL_DESCRIPTOR_LEN
+            {
+                if let Some(func_desc) = FunctionalDescriptor::from_bytes(descriptor) {
+                    return Some(func_desc.map_err(Into::into));
+                }
             }
+
+            offset += length;
         }
 
         None
     }
 }
 
-fn explode_request_type(request_type: u8) -> (ControlType, Recipient) {
+/// Builds a [`Dfu`] with non-default control timeouts and retry
+/// behavior, for slow bootloaders and flaky hubs where a single fixed 3s
+/// timeout either stalls too long or aborts a legitimately slow erase/
+/// program cycle.
+///
+/// ```no_run
+/// # use dfu_nusb::DfuNusbBuilder;
+/// # use std::time::Duration;
+/// let dfu = DfuNusbBuilder::new()
+///     .control_timeout(Duration::from_secs(10))
+///     .retries(3, Duration::from_millis(100))
+///     .reset_on_drop(true)
+///     .open(0x0483, 0xdf11, 0, 0)?;
+/// # Ok::<(), dfu_nusb::Error>(())
+/// ```
+pub struct DfuNusbBuilder {
+    control_timeout: Duration,
+    string_timeout: Duration,
+    retries: u32,
+    retry_backoff: Duration,
+    reset_on_drop: bool,
+}
+
+impl Default for DfuNusbBuilder {
+    fn default() -> Self {
+        Self {
+            control_timeout: Duration::from_secs(3),
+            string_timeout: Duration::from_millis(1000),
+            retries: 0,
+            retry_backoff: Duration::from_millis(100),
+            reset_on_drop: false,
+        }
+    }
+}
+
+impl DfuNusbBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Timeout applied to every control transfer. Defaults to 3 seconds.
+    pub fn control_timeout(mut self, timeout: Duration) -> Self {
+        self.control_timeout = timeout;
+        self
+    }
+
+    /// Timeout applied to reading the alt-setting string descriptor.
+    /// Defaults to 1 second.
+    pub fn string_timeout(mut self, timeout: Duration) -> Self {
+        self.string_timeout = timeout;
+        self
+    }
+
+    /// Retries a control transfer up to `retries` times, waiting
+    /// `backoff * attempt` between attempts, when it comes back with
+    /// `TransferError::Stall` or `TransferError::Cancelled`. Defaults to
+    /// no retries.
+    pub fn retries(mut self, retries: u32, backoff: Duration) -> Self {
+        self.retries = retries;
+        self.retry_backoff = backoff;
+        self
+    }
+
+    /// Whether to issue `Device::reset()` when the opened [`DfuNusb`] is
+    /// dropped. Defaults to `false`.
+    pub fn reset_on_drop(mut self, reset_on_drop: bool) -> Self {
+        self.reset_on_drop = reset_on_drop;
+        self
+    }
+
+    pub fn open(self, vid: u16, pid: u16, iface: u8, alt: u8) -> Result<Dfu, Error> {
+        let device = DfuNusb::open_device(vid, pid)?;
+        self.from_usb_device(device, iface, alt)
+    }
+
+    pub fn from_usb_device(self, device: Device, iface_num: u8, alt: u8) -> Result<Dfu, Error> {
+        let iface = device.claim_interface(iface_num)?;
+        iface.set_alt_setting(alt)?;
+        for config in device.configurations() {
+            let Some(interface) = config.interfaces().find(|x| x.interface_number() == iface_num)
+            else {
+                continue;
+            };
+            let Some(setting) = interface
+                .alt_settings()
+                .find(|x| x.alternate_setting() == alt)
+            else {
+                continue;
+            };
+            let Some(func_desc) = DfuNusb::find_functional_descriptor(&config, iface_num, alt)
+                .transpose()?
+            else {
+                continue;
+            };
+            let Some(string_ix) = setting.string_index() else {
+                continue;
+            };
+            let iface_string = device
+                .get_string_descriptor(string_ix, 0, self.string_timeout)?
+                .trim_end_matches('\0')
+                .to_string();
+
+            let protocol = dfu_core::DfuProtocol::new(&iface_string, func_desc.dfu_version)?;
+
+            let io = DfuNusb {
+                dev: device.clone(),
+                iface,
+                protocol,
+                timeout: self.control_timeout,
+                functional_descriptor: func_desc,
+                retries: self.retries,
+                retry_backoff: self.retry_backoff,
+                reset_on_drop: self.reset_on_drop,
+            };
+
+            return Ok(dfu_core::sync::DfuSync::new(io));
+        }
+
+        Err(Error::NoDfuCapableDeviceFound)
+    }
+}
+
+pub(crate) fn explode_request_type(request_type: u8) -> (ControlType, Recipient) {
     let control_type = match (request_type >> 5) & 0b11 {
         0 => ControlType::Standard,
         1 => ControlType::Class,
@@ -193,3 +557,100 @@ fn explode_request_type(request_type: u8) -> (ControlType, Recipient) {
     };
     (control_type, recipient)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nusb::descriptors::Configuration;
+
+    fn interface_descriptor(num: u8, alt: u8, class: u8, subclass: u8, protocol: u8) -> Vec<u8> {
+        vec![9, DESC_TYPE_INTERFACE, num, alt, 0, class, subclass, protocol, 0]
+    }
+
+    fn functional_descriptor(detach_timeout: u16, transfer_size: u16) -> Vec<u8> {
+        let mut bytes = vec![
+            DFU_FUNCTIONAL_DESCRIPTOR_LEN as u8,
+            DESC_TYPE_DFU_FUNCTIONAL,
+            0b0000_1111, // can_download, can_upload, manifestation_tolerant, will_detach
+        ];
+        bytes.extend_from_slice(&detach_timeout.to_le_bytes());
+        bytes.extend_from_slice(&transfer_size.to_le_bytes());
+        bytes.extend_from_slice(&[0x1a, 0x01]); // bcdDFUVersion 1.1a
+        bytes
+    }
+
+    /// Wraps `body` (a series of concatenated descriptors) in a minimal
+    /// configuration descriptor header, as `Configuration::new` requires.
+    fn configuration(body: &[u8]) -> Vec<u8> {
+        let total_len = 9 + body.len();
+        let mut bytes = vec![
+            9,
+            DESC_TYPE_CONFIGURATION,
+            (total_len & 0xff) as u8,
+            (total_len >> 8) as u8,
+            1,
+            1,
+            0,
+            0,
+            0,
+        ];
+        bytes.extend_from_slice(body);
+        bytes
+    }
+
+    #[test]
+    fn finds_functional_descriptor_in_composite_device() {
+        let mut body = interface_descriptor(0, 0, 0x03, 0x00, 0x00); // unrelated HID interface
+        body.extend(interface_descriptor(1, 0, DFU_INTERFACE_CLASS, DFU_INTERFACE_SUBCLASS, 2));
+        body.extend(functional_descriptor(1000, 2048));
+        let config_bytes = configuration(&body);
+        let config = Configuration::new(&config_bytes);
+
+        let func_desc = DfuNusb::find_functional_descriptor(&config, 1, 0)
+            .expect("functional descriptor should be found")
+            .expect("functional descriptor should parse");
+        assert_eq!(func_desc.transfer_size, 2048);
+        assert_eq!(func_desc.detach_timeout, 1000);
+
+        assert!(DfuNusb::find_functional_descriptor(&config, 0, 0).is_none());
+    }
+
+    #[test]
+    fn stops_at_a_zero_length_descriptor_instead_of_looping_forever() {
+        let mut body = interface_descriptor(0, 0, DFU_INTERFACE_CLASS, DFU_INTERFACE_SUBCLASS, 2);
+        body.push(0); // bLength == 0
+        body.push(DESC_TYPE_DFU_FUNCTIONAL);
+        body.extend(functional_descriptor(1000, 2048));
+        let config_bytes = configuration(&body);
+        let config = Configuration::new(&config_bytes);
+
+        // The walker must bail out at the zero-length descriptor rather
+        // than looping forever or reading past it to the (unreachable)
+        // functional descriptor that follows.
+        assert!(DfuNusb::find_functional_descriptor(&config, 0, 0).is_none());
+    }
+
+    #[test]
+    fn stops_at_a_truncated_trailing_descriptor() {
+        let mut body = interface_descriptor(0, 0, DFU_INTERFACE_CLASS, DFU_INTERFACE_SUBCLASS, 2);
+        // Claims to be a full 9-byte functional descriptor but only 2 bytes
+        // are actually present.
+        body.extend_from_slice(&[DFU_FUNCTIONAL_DESCRIPTOR_LEN as u8, DESC_TYPE_DFU_FUNCTIONAL]);
+        let config_bytes = configuration(&body);
+        let config = Configuration::new(&config_bytes);
+
+        assert!(DfuNusb::find_functional_descriptor(&config, 0, 0).is_none());
+    }
+
+    #[test]
+    fn ignores_a_functional_descriptor_under_a_different_interface() {
+        let mut body = interface_descriptor(0, 0, DFU_INTERFACE_CLASS, DFU_INTERFACE_SUBCLASS, 2);
+        body.extend(functional_descriptor(1000, 2048));
+        body.extend(interface_descriptor(1, 0, DFU_INTERFACE_CLASS, DFU_INTERFACE_SUBCLASS, 2));
+        let config_bytes = configuration(&body);
+        let config = Configuration::new(&config_bytes);
+
+        assert!(DfuNusb::find_functional_descriptor(&config, 1, 0).is_none());
+        assert!(DfuNusb::find_functional_descriptor(&config, 0, 0).is_some());
+    }
+}